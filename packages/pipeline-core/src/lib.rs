@@ -3,6 +3,7 @@
 
 use napi::bindgen_prelude::Buffer;
 use napi_derive::napi;
+use std::collections::HashMap;
 
 /// Compute horizontal projection profile (sum of pixels per row).
 pub fn projection_profile_y(data: &[u8], width: usize, height: usize) -> Vec<u32> {
@@ -172,18 +173,9 @@ fn gradient_histogram(data: &[u8], width: usize, height: usize) -> [f64; 181] {
     histogram
 }
 
-#[napi(js_name = "estimateSkewAngle")]
-pub fn estimate_skew_angle_js(data: Buffer, width: u32, height: u32) -> DeskewEstimate {
-    let width = width as usize;
-    let height = height as usize;
-    let bytes = data.as_ref();
-    if width == 0 || height == 0 || bytes.len() < width * height {
-        return DeskewEstimate {
-            angle: 0.0,
-            confidence: 0.0,
-        };
-    }
-    let histogram = gradient_histogram(&bytes[..width * height], width, height);
+/// Single-peak gradient-histogram deskew estimate (the original algorithm).
+fn estimate_skew_gradient(data: &[u8], width: usize, height: usize) -> DeskewEstimate {
+    let histogram = gradient_histogram(data, width, height);
     let mut best_bucket = 90usize;
     let mut best_val = 0f64;
     for (idx, val) in histogram.iter().enumerate() {
@@ -206,6 +198,20 @@ pub fn estimate_skew_angle_js(data: Buffer, width: u32, height: u32) -> DeskewEs
     DeskewEstimate { angle, confidence }
 }
 
+#[napi(js_name = "estimateSkewAngle")]
+pub fn estimate_skew_angle_js(data: Buffer, width: u32, height: u32) -> DeskewEstimate {
+    let width = width as usize;
+    let height = height as usize;
+    let bytes = data.as_ref();
+    if width == 0 || height == 0 || bytes.len() < width * height {
+        return DeskewEstimate {
+            angle: 0.0,
+            confidence: 0.0,
+        };
+    }
+    estimate_skew_gradient(&bytes[..width * height], width, height)
+}
+
 #[napi(js_name = "baselineMetrics")]
 pub fn baseline_metrics_js(data: Buffer, width: u32, height: u32) -> BaselineMetricsResult {
     let width = width as usize;
@@ -536,6 +542,861 @@ pub fn dhash_9x8_js(data: Buffer) -> String {
     format!("{:016x}", hash)
 }
 
+/// Bilinearly sample `data` at `(x, y)`, returning `fill` when out of bounds.
+fn bilinear_sample(data: &[u8], width: usize, height: usize, x: f64, y: f64, fill: u8) -> u8 {
+    if x < 0.0 || y < 0.0 || width == 0 || height == 0 {
+        return fill;
+    }
+    let x0 = x.floor();
+    let y0 = y.floor();
+    if x0 as usize >= width - 1 || y0 as usize >= height - 1 {
+        // Allow the exact bottom/right edge to sample without falling back to fill.
+        if x0 as usize >= width || y0 as usize >= height {
+            return fill;
+        }
+        let xi = (x0 as usize).min(width - 1);
+        let yi = (y0 as usize).min(height - 1);
+        return data[yi * width + xi];
+    }
+    let xi = x0 as usize;
+    let yi = y0 as usize;
+    let fx = x - x0;
+    let fy = y - y0;
+    let p00 = data[yi * width + xi] as f64;
+    let p10 = data[yi * width + xi + 1] as f64;
+    let p01 = data[(yi + 1) * width + xi] as f64;
+    let p11 = data[(yi + 1) * width + xi + 1] as f64;
+    let top = p00 + (p10 - p00) * fx;
+    let bottom = p01 + (p11 - p01) * fx;
+    (top + (bottom - top) * fy).round().clamp(0.0, 255.0) as u8
+}
+
+/// Solve for the 3x3 projective matrix (row-major, `h[8] = 1`) mapping `src` onto `dst`.
+fn solve_homography(src: [(f64, f64); 4], dst: [(f64, f64); 4]) -> Option<[f64; 9]> {
+    let mut a = [[0f64; 8]; 8];
+    let mut b = [0f64; 8];
+    for i in 0..4 {
+        let (x, y) = src[i];
+        let (u, v) = dst[i];
+        a[2 * i] = [x, y, 1.0, 0.0, 0.0, 0.0, -u * x, -u * y];
+        b[2 * i] = u;
+        a[2 * i + 1] = [0.0, 0.0, 0.0, x, y, 1.0, -v * x, -v * y];
+        b[2 * i + 1] = v;
+    }
+    let h = solve_linear_system(a, b)?;
+    Some([h[0], h[1], h[2], h[3], h[4], h[5], h[6], h[7], 1.0])
+}
+
+/// Gaussian elimination with partial pivoting for an 8x8 linear system.
+fn solve_linear_system(mut a: [[f64; 8]; 8], mut b: [f64; 8]) -> Option<[f64; 8]> {
+    for col in 0..8 {
+        let mut pivot_row = col;
+        let mut pivot_val = a[col][col].abs();
+        for (row, row_vals) in a.iter().enumerate().skip(col + 1) {
+            if row_vals[col].abs() > pivot_val {
+                pivot_val = row_vals[col].abs();
+                pivot_row = row;
+            }
+        }
+        if pivot_val < 1e-9 {
+            return None;
+        }
+        a.swap(col, pivot_row);
+        b.swap(col, pivot_row);
+        let pivot = a[col][col];
+        let pivot_row_vals = a[col];
+        for row in (col + 1)..8 {
+            let factor = a[row][col] / pivot;
+            if factor == 0.0 {
+                continue;
+            }
+            for (a_rk, p_k) in a[row].iter_mut().zip(pivot_row_vals.iter()).skip(col) {
+                *a_rk -= factor * p_k;
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+    let mut x = [0f64; 8];
+    for row in (0..8).rev() {
+        let mut sum = b[row];
+        for k in (row + 1)..8 {
+            sum -= a[row][k] * x[k];
+        }
+        x[row] = sum / a[row][row];
+    }
+    Some(x)
+}
+
+struct HoughLine {
+    theta_deg: usize,
+    rho: f64,
+    votes: u32,
+}
+
+/// Accumulate a classic (theta, rho) Hough transform over the edge mask.
+fn hough_accumulate(edges: &[bool], width: usize, height: usize) -> (Vec<u32>, i64) {
+    let diag = ((width * width + height * height) as f64).sqrt();
+    let rho_max = diag.ceil() as i64 + 1;
+    let rho_buckets = (2 * rho_max + 1) as usize;
+    let mut cos_table = [0f64; 180];
+    let mut sin_table = [0f64; 180];
+    for (t, (c, s)) in cos_table.iter_mut().zip(sin_table.iter_mut()).enumerate() {
+        let rad = (t as f64).to_radians();
+        *c = rad.cos();
+        *s = rad.sin();
+    }
+    let mut accumulator = vec![0u32; 180 * rho_buckets];
+    for y in 0..height {
+        for x in 0..width {
+            if !edges[y * width + x] {
+                continue;
+            }
+            for t in 0..180 {
+                let rho = x as f64 * cos_table[t] + y as f64 * sin_table[t];
+                let bucket = rho.round() as i64 + rho_max;
+                if bucket >= 0 && (bucket as usize) < rho_buckets {
+                    accumulator[t * rho_buckets + bucket as usize] += 1;
+                }
+            }
+        }
+    }
+    (accumulator, rho_max)
+}
+
+/// Pick up to two strongest lines with theta satisfying `angle_ok`, at least `min_rho_sep` apart.
+fn pick_strongest_lines(
+    accumulator: &[u32],
+    rho_max: i64,
+    angle_ok: impl Fn(usize) -> bool,
+    min_rho_sep: f64,
+) -> Vec<HoughLine> {
+    let rho_buckets = (2 * rho_max + 1) as usize;
+    let mut candidates: Vec<HoughLine> = Vec::new();
+    for t in 0..180 {
+        if !angle_ok(t) {
+            continue;
+        }
+        for r in 0..rho_buckets {
+            let votes = accumulator[t * rho_buckets + r];
+            if votes == 0 {
+                continue;
+            }
+            candidates.push(HoughLine {
+                theta_deg: t,
+                rho: r as f64 - rho_max as f64,
+                votes,
+            });
+        }
+    }
+    candidates.sort_by_key(|c| std::cmp::Reverse(c.votes));
+    let mut picked: Vec<HoughLine> = Vec::new();
+    for candidate in candidates {
+        if picked.iter().any(|p| (p.rho - candidate.rho).abs() < min_rho_sep) {
+            continue;
+        }
+        picked.push(candidate);
+        if picked.len() == 2 {
+            break;
+        }
+    }
+    picked
+}
+
+/// Intersect two Hough lines (`x*cos(theta) + y*sin(theta) = rho`); `None` if parallel.
+fn intersect_lines(a: &HoughLine, b: &HoughLine) -> Option<(f64, f64)> {
+    let theta_a = (a.theta_deg as f64).to_radians();
+    let theta_b = (b.theta_deg as f64).to_radians();
+    let (cos_a, sin_a) = (theta_a.cos(), theta_a.sin());
+    let (cos_b, sin_b) = (theta_b.cos(), theta_b.sin());
+    let det = cos_a * sin_b - cos_b * sin_a;
+    if det.abs() < 1e-6 {
+        return None;
+    }
+    let x = (a.rho * sin_b - b.rho * sin_a) / det;
+    let y = (cos_a * b.rho - cos_b * a.rho) / det;
+    Some((x, y))
+}
+
+/// Detect the page quadrilateral and rectify it to an axis-aligned rectangle.
+pub fn dewarp_perspective(
+    data: &[u8],
+    width: usize,
+    height: usize,
+) -> (Vec<u8>, [(f64, f64); 4], f64) {
+    let identity_corners = [
+        (0.0, 0.0),
+        ((width.max(1) - 1) as f64, 0.0),
+        ((width.max(1) - 1) as f64, (height.max(1) - 1) as f64),
+        (0.0, (height.max(1) - 1) as f64),
+    ];
+    if width < 8 || height < 8 {
+        return (data.to_vec(), identity_corners, 0.0);
+    }
+
+    let magnitude = sobel_magnitude(data, width, height);
+    let max_mag = magnitude.iter().copied().max().unwrap_or(0);
+    if max_mag == 0 {
+        return (data.to_vec(), identity_corners, 0.0);
+    }
+    let threshold = (max_mag as f64 * 0.35) as u16;
+    let edges: Vec<bool> = magnitude.iter().map(|m| *m >= threshold.max(1)).collect();
+
+    let (accumulator, rho_max) = hough_accumulate(&edges, width, height);
+    let diag = ((width * width + height * height) as f64).sqrt();
+    let min_sep = (diag * 0.05).max(2.0);
+
+    let horizontal = pick_strongest_lines(
+        &accumulator,
+        rho_max,
+        |t| (t as i32 - 90).abs() <= 20,
+        min_sep,
+    );
+    let vertical = pick_strongest_lines(
+        &accumulator,
+        rho_max,
+        |t| t <= 20 || t >= 160,
+        min_sep,
+    );
+
+    if horizontal.len() < 2 || vertical.len() < 2 {
+        return (data.to_vec(), identity_corners, 0.0);
+    }
+
+    let mut corners: Vec<(f64, f64)> = Vec::with_capacity(4);
+    for h in &horizontal {
+        for v in &vertical {
+            match intersect_lines(h, v) {
+                Some(point) => corners.push(point),
+                None => return (data.to_vec(), identity_corners, 0.0),
+            }
+        }
+    }
+    if corners.len() != 4 {
+        return (data.to_vec(), identity_corners, 0.0);
+    }
+
+    // Order as [TL, TR, BR, BL] using the sum/difference trick.
+    corners.sort_by(|a, b| {
+        (a.0 + a.1)
+            .partial_cmp(&(b.0 + b.1))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    let (tl, br) = (corners[0], corners[3]);
+    let mut mid_pair = [corners[1], corners[2]];
+    mid_pair.sort_by(|a, b| {
+        (a.0 - a.1)
+            .partial_cmp(&(b.0 - b.1))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    let (bl, tr) = (mid_pair[0], mid_pair[1]);
+    let ordered = [tl, tr, br, bl];
+
+    let margin = (diag * 0.01).clamp(1.0, 8.0);
+    let dst = [
+        (margin, margin),
+        ((width - 1) as f64 - margin, margin),
+        ((width - 1) as f64 - margin, (height - 1) as f64 - margin),
+        (margin, (height - 1) as f64 - margin),
+    ];
+
+    // Solve the inverse mapping (destination -> source) directly so filling
+    // the output only needs a single matrix application per pixel.
+    let inverse = match solve_homography(dst, ordered) {
+        Some(h) => h,
+        None => return (data.to_vec(), identity_corners, 0.0),
+    };
+
+    let mut out = vec![255u8; width * height];
+    for y in 0..height {
+        for x in 0..width {
+            let (fx, fy) = (x as f64, y as f64);
+            let denom = inverse[6] * fx + inverse[7] * fy + inverse[8];
+            if denom.abs() < 1e-9 {
+                continue;
+            }
+            let sx = (inverse[0] * fx + inverse[1] * fy + inverse[2]) / denom;
+            let sy = (inverse[3] * fx + inverse[4] * fy + inverse[5]) / denom;
+            out[y * width + x] = bilinear_sample(data, width, height, sx, sy, 255);
+        }
+    }
+
+    let total_votes: f64 = (horizontal.iter().chain(vertical.iter()))
+        .map(|l| l.votes as f64)
+        .sum();
+    let edge_count = edges.iter().filter(|e| **e).count().max(1) as f64;
+    let confidence = (total_votes / edge_count).min(1.0);
+
+    (out, ordered, confidence)
+}
+
+#[napi(object)]
+pub struct DewarpResult {
+    pub data: Buffer,
+    #[napi(js_name = "corners")]
+    pub corners: Vec<f64>,
+    pub confidence: f64,
+}
+
+#[napi(js_name = "dewarpPerspective")]
+pub fn dewarp_perspective_js(data: Buffer, width: u32, height: u32) -> DewarpResult {
+    let width = width as usize;
+    let height = height as usize;
+    let bytes = data.as_ref();
+    if width == 0 || height == 0 || bytes.len() < width * height {
+        return DewarpResult {
+            data: Buffer::from(bytes.to_vec()),
+            corners: vec![],
+            confidence: 0.0,
+        };
+    }
+    let (warped, corners, confidence) = dewarp_perspective(&bytes[..width * height], width, height);
+    let flat_corners: Vec<f64> = corners.iter().flat_map(|(x, y)| [*x, *y]).collect();
+    DewarpResult {
+        data: Buffer::from(warped),
+        corners: flat_corners,
+        confidence,
+    }
+}
+
+/// Large diamond search pattern (LDSP): axis points at distance 2, diagonal points at distance 1.
+const LDSP_POINTS: [(i32, i32); 8] = [
+    (2, 0),
+    (-2, 0),
+    (0, 2),
+    (0, -2),
+    (1, 1),
+    (1, -1),
+    (-1, 1),
+    (-1, -1),
+];
+
+/// Small diamond search pattern (SDSP); also used as the small-hexagon refine step.
+const SDSP_POINTS: [(i32, i32); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+/// Large hexagon search pattern (LHSP): horizontal points at distance 2, four at `(±1, ±2)`.
+const LHSP_POINTS: [(i32, i32); 6] = [(2, 0), (-2, 0), (1, 2), (1, -2), (-1, 2), (-1, -2)];
+
+/// SAD between `ref_data` and `mov_data` shifted by `(dx, dy)`, normalized by overlap area.
+fn translation_sad(ref_data: &[u8], mov_data: &[u8], width: usize, height: usize, dx: i32, dy: i32) -> f64 {
+    let mut sad = 0i64;
+    let mut count = 0i64;
+    for y in 0..height as i32 {
+        let my = y + dy;
+        if my < 0 || my >= height as i32 {
+            continue;
+        }
+        for x in 0..width as i32 {
+            let mx = x + dx;
+            if mx < 0 || mx >= width as i32 {
+                continue;
+            }
+            let r = ref_data[y as usize * width + x as usize] as i64;
+            let m = mov_data[my as usize * width + mx as usize] as i64;
+            sad += (r - m).abs();
+            count += 1;
+        }
+    }
+    if count == 0 {
+        f64::MAX
+    } else {
+        sad as f64 / count as f64
+    }
+}
+
+/// Evaluate `center` plus every point in `pattern`, returning the best `((dx, dy), cost)`.
+fn evaluate_pattern(
+    cache: &mut HashMap<(i32, i32), f64>,
+    mut cost_fn: impl FnMut(i32, i32) -> f64,
+    center: (i32, i32),
+    pattern: &[(i32, i32)],
+) -> ((i32, i32), f64) {
+    let mut eval = |p: (i32, i32)| -> f64 {
+        *cache.entry(p).or_insert_with(|| cost_fn(p.0, p.1))
+    };
+    let mut best = center;
+    let mut best_cost = eval(center);
+    for offset in pattern {
+        let p = (center.0 + offset.0, center.1 + offset.1);
+        let cost = eval(p);
+        if cost < best_cost {
+            best_cost = cost;
+            best = p;
+        }
+    }
+    (best, best_cost)
+}
+
+/// Step `center` towards the best neighbor in `pattern` until it is the local minimum.
+fn iterative_pattern_search(
+    cache: &mut HashMap<(i32, i32), f64>,
+    cost_fn: impl Fn(i32, i32) -> f64 + Copy,
+    start: (i32, i32),
+    pattern: &[(i32, i32)],
+    max_iters: usize,
+) -> (i32, i32) {
+    let mut center = start;
+    for _ in 0..max_iters {
+        let (best, _) = evaluate_pattern(cache, cost_fn, center, pattern);
+        if best == center {
+            break;
+        }
+        center = best;
+    }
+    center
+}
+
+/// Probe both axes independently and combine the best x/y offsets into one diagonal start point.
+fn cross_search(
+    cache: &mut HashMap<(i32, i32), f64>,
+    cost_fn: impl Fn(i32, i32) -> f64 + Copy,
+    start: (i32, i32),
+    range: i32,
+) -> (i32, i32) {
+    let mut eval = |p: (i32, i32)| -> f64 { *cache.entry(p).or_insert_with(|| cost_fn(p.0, p.1)) };
+    let mut best_x = start.0;
+    let mut best_x_cost = eval(start);
+    let mut best_y = start.1;
+    let mut best_y_cost = best_x_cost;
+    for i in -range..=range {
+        if i == 0 {
+            continue;
+        }
+        let horizontal_cost = eval((start.0 + i, start.1));
+        if horizontal_cost < best_x_cost {
+            best_x_cost = horizontal_cost;
+            best_x = start.0 + i;
+        }
+        let vertical_cost = eval((start.0, start.1 + i));
+        if vertical_cost < best_y_cost {
+            best_y_cost = vertical_cost;
+            best_y = start.1 + i;
+        }
+    }
+    (best_x, best_y)
+}
+
+/// Sample six points evenly spaced around `center` at each radius in `radii`.
+fn multi_hexagon_grid_search(
+    cache: &mut HashMap<(i32, i32), f64>,
+    cost_fn: impl Fn(i32, i32) -> f64 + Copy,
+    start: (i32, i32),
+    radii: &[i32],
+) -> (i32, i32) {
+    let mut points: Vec<(i32, i32)> = Vec::new();
+    for radius in radii {
+        for k in 0..6 {
+            let angle = (k as f64) * std::f64::consts::FRAC_PI_3;
+            let ox = (*radius as f64 * angle.cos()).round() as i32;
+            let oy = (*radius as f64 * angle.sin()).round() as i32;
+            if ox != 0 || oy != 0 {
+                points.push((ox, oy));
+            }
+        }
+    }
+    let (best, _) = evaluate_pattern(cache, cost_fn, start, &points);
+    best
+}
+
+/// Find the `(dx, dy)` offset and residual cost that best aligns `mov_data` onto `ref_data`.
+pub fn register_translation(
+    ref_data: &[u8],
+    mov_data: &[u8],
+    width: usize,
+    height: usize,
+    mode: &str,
+) -> (i32, i32, f64) {
+    let mut cache: HashMap<(i32, i32), f64> = HashMap::new();
+    let cost_fn = |dx: i32, dy: i32| translation_sad(ref_data, mov_data, width, height, dx, dy);
+    let max_iters = 32;
+
+    let center = match mode {
+        "hexagon" => {
+            let big = iterative_pattern_search(&mut cache, cost_fn, (0, 0), &LHSP_POINTS, max_iters);
+            let (refined, _) = evaluate_pattern(&mut cache, cost_fn, big, &SDSP_POINTS);
+            refined
+        }
+        "umh" => {
+            let range = (width.min(height) as i32 / 4).max(4);
+            let after_cross = cross_search(&mut cache, cost_fn, (0, 0), range);
+            let after_mhgs =
+                multi_hexagon_grid_search(&mut cache, cost_fn, after_cross, &[2, 4, 6, 8]);
+            let after_hex =
+                iterative_pattern_search(&mut cache, cost_fn, after_mhgs, &LHSP_POINTS, max_iters);
+            let (refined, _) = evaluate_pattern(&mut cache, cost_fn, after_hex, &SDSP_POINTS);
+            refined
+        }
+        _ => {
+            let big = iterative_pattern_search(&mut cache, cost_fn, (0, 0), &LDSP_POINTS, max_iters);
+            let (refined, _) = evaluate_pattern(&mut cache, cost_fn, big, &SDSP_POINTS);
+            refined
+        }
+    };
+    let cost = *cache
+        .entry(center)
+        .or_insert_with(|| cost_fn(center.0, center.1));
+    (center.0, center.1, cost)
+}
+
+#[napi(object)]
+pub struct RegistrationResult {
+    pub dx: i32,
+    pub dy: i32,
+    pub cost: f64,
+}
+
+#[napi(js_name = "registerTranslation")]
+pub fn register_translation_js(
+    ref_data: Buffer,
+    mov_data: Buffer,
+    width: u32,
+    height: u32,
+    mode: String,
+) -> RegistrationResult {
+    let width = width as usize;
+    let height = height as usize;
+    let ref_bytes = ref_data.as_ref();
+    let mov_bytes = mov_data.as_ref();
+    let size = width * height;
+    if width == 0 || height == 0 || ref_bytes.len() < size || mov_bytes.len() < size {
+        return RegistrationResult {
+            dx: 0,
+            dy: 0,
+            cost: f64::MAX,
+        };
+    }
+    let (dx, dy, cost) = register_translation(
+        &ref_bytes[..size],
+        &mov_bytes[..size],
+        width,
+        height,
+        &mode,
+    );
+    RegistrationResult { dx, dy, cost }
+}
+
+/// Box-average `data` down to a `target x target` grid of row-major f64 intensities.
+fn downsample_box(data: &[u8], width: usize, height: usize, target: usize) -> Vec<f64> {
+    let mut out = vec![0f64; target * target];
+    for ty in 0..target {
+        let y0 = ty * height / target;
+        let y1 = (((ty + 1) * height / target).max(y0 + 1)).min(height);
+        for tx in 0..target {
+            let x0 = tx * width / target;
+            let x1 = (((tx + 1) * width / target).max(x0 + 1)).min(width);
+            let mut sum = 0f64;
+            let mut count = 0usize;
+            for y in y0..y1 {
+                for x in x0..x1 {
+                    sum += data[y * width + x] as f64;
+                    count += 1;
+                }
+            }
+            out[ty * target + tx] = if count > 0 { sum / count as f64 } else { 0.0 };
+        }
+    }
+    out
+}
+
+/// 1D DCT-II of `input`, normalized so the basis vectors are orthonormal.
+fn dct_1d(input: &[f64]) -> Vec<f64> {
+    let n = input.len();
+    let mut out = vec![0f64; n];
+    for (u, slot) in out.iter_mut().enumerate() {
+        let mut sum = 0f64;
+        for (x, value) in input.iter().enumerate() {
+            let angle = std::f64::consts::PI / n as f64 * (x as f64 + 0.5) * u as f64;
+            sum += value * angle.cos();
+        }
+        let alpha = if u == 0 {
+            (1.0 / n as f64).sqrt()
+        } else {
+            (2.0 / n as f64).sqrt()
+        };
+        *slot = alpha * sum;
+    }
+    out
+}
+
+/// Separable 2D DCT-II over an `n x n` row-major block.
+fn dct_2d(block: &[f64], n: usize) -> Vec<f64> {
+    let mut rows = vec![0f64; n * n];
+    for y in 0..n {
+        let row = dct_1d(&block[y * n..(y + 1) * n]);
+        rows[y * n..(y + 1) * n].copy_from_slice(&row);
+    }
+    let mut out = vec![0f64; n * n];
+    for x in 0..n {
+        let column: Vec<f64> = (0..n).map(|y| rows[y * n + x]).collect();
+        let column_dct = dct_1d(&column);
+        for (y, value) in column_dct.into_iter().enumerate() {
+            out[y * n + x] = value;
+        }
+    }
+    out
+}
+
+/// Median of an already-sorted slice (average of the two middle values when even-length).
+fn median_of_sorted(sorted: &[f64]) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    if sorted.len().is_multiple_of(2) {
+        let mid = sorted.len() / 2;
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[sorted.len() / 2]
+    }
+}
+
+/// Perceptual hash: DCT-II over a 32x32 downsample, bit `i` set when the top-left coefficient `i` exceeds the median.
+pub fn p_hash(data: &[u8], width: usize, height: usize) -> u64 {
+    if width == 0 || height == 0 || data.len() < width * height {
+        return 0;
+    }
+    const DOWNSAMPLE: usize = 32;
+    const BLOCK: usize = 8;
+    let small = downsample_box(&data[..width * height], width, height, DOWNSAMPLE);
+    let freq = dct_2d(&small, DOWNSAMPLE);
+
+    let mut coeffs = [0f64; BLOCK * BLOCK];
+    for y in 0..BLOCK {
+        for x in 0..BLOCK {
+            coeffs[y * BLOCK + x] = freq[y * DOWNSAMPLE + x];
+        }
+    }
+
+    let mut without_dc: Vec<f64> = coeffs[1..].to_vec();
+    without_dc.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let median = median_of_sorted(&without_dc);
+
+    let mut hash = 0u64;
+    for (i, coeff) in coeffs.iter().enumerate() {
+        if *coeff > median {
+            hash |= 1u64 << i;
+        }
+    }
+    hash
+}
+
+#[napi(js_name = "pHash")]
+pub fn p_hash_js(data: Buffer, width: u32, height: u32) -> String {
+    let width = width as usize;
+    let height = height as usize;
+    let bytes = data.as_ref();
+    if width == 0 || height == 0 || bytes.len() < width * height {
+        return "0".to_string();
+    }
+    format!("{:016x}", p_hash(bytes, width, height))
+}
+
+#[napi(js_name = "hammingDistance")]
+pub fn hamming_distance_js(hash_a: String, hash_b: String) -> u32 {
+    let a = u64::from_str_radix(&hash_a, 16).unwrap_or(0);
+    let b = u64::from_str_radix(&hash_b, 16).unwrap_or(0);
+    (a ^ b).count_ones()
+}
+
+/// Ink projection profile for candidate rotation `angle_deg`, via shear rather than rotation.
+fn sheared_projection_profile(data: &[u8], width: usize, height: usize, angle_deg: f64) -> Vec<f64> {
+    let tan_theta = angle_deg.to_radians().tan();
+    let max_shift = (width as f64 * tan_theta.abs()).ceil() as i64 + 1;
+    let offset = max_shift;
+    let size = (height as i64 + 2 * max_shift).max(0) as usize;
+    let mut profile = vec![0f64; size];
+    for y in 0..height {
+        let row_offset = y * width;
+        for x in 0..width {
+            let ink = 255.0 - data[row_offset + x] as f64;
+            let bucket = (y as f64 - x as f64 * tan_theta).round() as i64 + offset;
+            if bucket >= 0 && (bucket as usize) < profile.len() {
+                profile[bucket as usize] += ink;
+            }
+        }
+    }
+    profile
+}
+
+/// Variance of adjacent-row differences; well-aligned text lines score high.
+fn profile_variance_score(profile: &[f64]) -> f64 {
+    if profile.len() < 2 {
+        return 0.0;
+    }
+    let diffs: Vec<f64> = profile.windows(2).map(|w| w[1] - w[0]).collect();
+    let mean = diffs.iter().sum::<f64>() / diffs.len() as f64;
+    diffs.iter().map(|d| (d - mean) * (d - mean)).sum::<f64>() / diffs.len() as f64
+}
+
+/// Coarse-to-fine rotation search, scored by sheared-projection-profile variance.
+fn estimate_skew_projection(data: &[u8], width: usize, height: usize) -> DeskewEstimate {
+    let score_angle = |angle: f64| -> f64 {
+        profile_variance_score(&sheared_projection_profile(data, width, height, angle))
+    };
+
+    let mut coarse_angle = -8.0f64;
+    let mut coarse_scores: Vec<(f64, f64)> = Vec::new();
+    while coarse_angle <= 8.0 + 1e-9 {
+        coarse_scores.push((coarse_angle, score_angle(coarse_angle)));
+        coarse_angle += 0.5;
+    }
+    let best_coarse = coarse_scores
+        .iter()
+        .cloned()
+        .fold((0.0, f64::MIN), |best, cand| if cand.1 > best.1 { cand } else { best });
+
+    let mut fine_angle = best_coarse.0 - 0.5;
+    let mut fine_scores: Vec<(f64, f64)> = Vec::new();
+    while fine_angle <= best_coarse.0 + 0.5 + 1e-9 {
+        fine_scores.push((fine_angle, score_angle(fine_angle)));
+        fine_angle += 0.1;
+    }
+    let best_fine = fine_scores
+        .iter()
+        .cloned()
+        .fold((0.0, f64::MIN), |best, cand| if cand.1 > best.1 { cand } else { best });
+
+    let mean_score = coarse_scores
+        .iter()
+        .chain(fine_scores.iter())
+        .map(|(_, score)| *score)
+        .sum::<f64>()
+        / (coarse_scores.len() + fine_scores.len()) as f64;
+    let confidence = if mean_score > 0.0 {
+        (best_fine.1 / mean_score).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+
+    DeskewEstimate {
+        angle: best_fine.0,
+        confidence,
+    }
+}
+
+/// Like `estimateSkewAngle`, but with a selectable `"gradient"` or `"projection"` method.
+#[napi(js_name = "estimateSkewAngleEx")]
+pub fn estimate_skew_angle_ex_js(
+    data: Buffer,
+    width: u32,
+    height: u32,
+    method: String,
+) -> DeskewEstimate {
+    let width = width as usize;
+    let height = height as usize;
+    let bytes = data.as_ref();
+    if width == 0 || height == 0 || bytes.len() < width * height {
+        return DeskewEstimate {
+            angle: 0.0,
+            confidence: 0.0,
+        };
+    }
+    let bytes = &bytes[..width * height];
+    match method.as_str() {
+        "projection" => estimate_skew_projection(bytes, width, height),
+        _ => estimate_skew_gradient(bytes, width, height),
+    }
+}
+
+/// Rotate `data` about its center by `-angle_deg` via bilinear sampling, growing the canvas if `expand`.
+pub fn apply_deskew(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    angle_deg: f64,
+    fill: u8,
+    expand: bool,
+) -> (Vec<u8>, usize, usize) {
+    if width == 0 || height == 0 {
+        return (Vec::new(), width, height);
+    }
+    let theta = (-angle_deg).to_radians();
+    let (cos_t, sin_t) = (theta.cos(), theta.sin());
+    let cx = (width as f64 - 1.0) / 2.0;
+    let cy = (height as f64 - 1.0) / 2.0;
+
+    let rotate = |x: f64, y: f64| -> (f64, f64) {
+        let dx = x - cx;
+        let dy = y - cy;
+        (cx + dx * cos_t - dy * sin_t, cy + dx * sin_t + dy * cos_t)
+    };
+
+    let (out_width, out_height, min_x, min_y) = if expand {
+        let corners = [
+            (0.0, 0.0),
+            ((width - 1) as f64, 0.0),
+            ((width - 1) as f64, (height - 1) as f64),
+            (0.0, (height - 1) as f64),
+        ];
+        let rotated: Vec<(f64, f64)> = corners.iter().map(|(x, y)| rotate(*x, *y)).collect();
+        let min_x = rotated.iter().map(|(x, _)| *x).fold(f64::MAX, f64::min);
+        let max_x = rotated.iter().map(|(x, _)| *x).fold(f64::MIN, f64::max);
+        let min_y = rotated.iter().map(|(_, y)| *y).fold(f64::MAX, f64::min);
+        let max_y = rotated.iter().map(|(_, y)| *y).fold(f64::MIN, f64::max);
+        let out_width = (max_x - min_x).round() as usize + 1;
+        let out_height = (max_y - min_y).round() as usize + 1;
+        (out_width, out_height, min_x, min_y)
+    } else {
+        (width, height, 0.0, 0.0)
+    };
+
+    // Inverse mapping: for each destination pixel, rotate back by `+theta`
+    // to find the source coordinate to sample.
+    let (cos_inv, sin_inv) = ((-theta).cos(), (-theta).sin());
+    let mut out = vec![fill; out_width * out_height];
+    for oy in 0..out_height {
+        for ox in 0..out_width {
+            let px = ox as f64 + min_x;
+            let py = oy as f64 + min_y;
+            let dx = px - cx;
+            let dy = py - cy;
+            let sx = cx + dx * cos_inv - dy * sin_inv;
+            let sy = cy + dx * sin_inv + dy * cos_inv;
+            out[oy * out_width + ox] = bilinear_sample(data, width, height, sx, sy, fill);
+        }
+    }
+    (out, out_width, out_height)
+}
+
+#[napi(object)]
+pub struct DeskewResult {
+    pub data: Buffer,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[napi(js_name = "applyDeskew")]
+pub fn apply_deskew_js(
+    data: Buffer,
+    width: u32,
+    height: u32,
+    angle_deg: f64,
+    fill: Option<u8>,
+    expand: Option<bool>,
+) -> DeskewResult {
+    let width = width as usize;
+    let height = height as usize;
+    let bytes = data.as_ref();
+    if width == 0 || height == 0 || bytes.len() < width * height {
+        return DeskewResult {
+            data: Buffer::from(bytes.to_vec()),
+            width: width as u32,
+            height: height as u32,
+        };
+    }
+    let (out, out_width, out_height) = apply_deskew(
+        &bytes[..width * height],
+        width,
+        height,
+        angle_deg,
+        fill.unwrap_or(255),
+        expand.unwrap_or(false),
+    );
+    DeskewResult {
+        data: Buffer::from(out),
+        width: out_width as u32,
+        height: out_height as u32,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -607,4 +1468,291 @@ mod tests {
         assert!(!elements.is_empty());
         assert!(elements.iter().any(|el| el.id == "page-bounds"));
     }
+
+    #[test]
+    fn dewarp_perspective_is_identity_on_blank_input() {
+        let width = 16;
+        let height = 16;
+        let data = vec![255u8; width * height];
+        let (out, _corners, confidence) = dewarp_perspective(&data, width, height);
+        assert_eq!(out.len(), width * height);
+        assert_eq!(out, data);
+        assert_eq!(confidence, 0.0);
+    }
+
+    #[test]
+    fn dewarp_perspective_detects_axis_aligned_rectangle() {
+        let width = 40;
+        let height = 40;
+        let mut data = vec![255u8; width * height];
+        for y in 5..35 {
+            for x in 5..35 {
+                if y == 5 || y == 34 || x == 5 || x == 34 {
+                    data[y * width + x] = 0;
+                }
+            }
+        }
+        let (out, corners, confidence) = dewarp_perspective(&data, width, height);
+        assert_eq!(out.len(), width * height);
+        assert_eq!(corners.len(), 4);
+        assert!(confidence > 0.3, "confidence too low: {confidence}");
+
+        // Corners should land close to the drawn square's edges at (5,5)-(34,34),
+        // in [TL, TR, BR, BL] order.
+        let expected = [(5.0, 5.0), (34.0, 5.0), (34.0, 34.0), (5.0, 34.0)];
+        for ((cx, cy), (ex, ey)) in corners.iter().zip(expected.iter()) {
+            assert!((cx - ex).abs() <= 2.0, "corner x {cx} too far from {ex}");
+            assert!((cy - ey).abs() <= 2.0, "corner y {cy} too far from {ey}");
+        }
+    }
+
+    #[test]
+    fn solve_homography_recovers_identity() {
+        let square = [(0.0, 0.0), (9.0, 0.0), (9.0, 9.0), (0.0, 9.0)];
+        let h = solve_homography(square, square).expect("solvable system");
+        let expected = [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0];
+        for (a, b) in h.iter().zip(expected.iter()) {
+            assert!((a - b).abs() < 1e-6);
+        }
+    }
+
+    /// Smooth, spatially-correlated test texture (a few out-of-phase sine
+    /// waves) so the SAD cost surface around the true offset is a convex
+    /// bowl, as it would be for real scanned-page content.
+    fn smooth_texture(width: usize, height: usize) -> Vec<u8> {
+        let mut data = vec![0u8; width * height];
+        for y in 0..height {
+            for x in 0..width {
+                let fx = x as f64;
+                let fy = y as f64;
+                let v = 128.0
+                    + 50.0 * (fx / 7.3).sin()
+                    + 50.0 * (fy / 5.1).sin()
+                    + 30.0 * ((fx + fy) / 11.7).sin();
+                data[y * width + x] = v.clamp(0.0, 255.0) as u8;
+            }
+        }
+        data
+    }
+
+    /// Same idea as `smooth_texture` but with longer periods, so the cost
+    /// surface stays unimodal across the larger displacement range tested by
+    /// `register_translation_recovers_large_diagonal_shift_for_each_mode`
+    /// (short periods would alias the SAD cost back down near the edge of
+    /// that search range, producing a spurious second minimum).
+    fn wide_period_texture(width: usize, height: usize) -> Vec<u8> {
+        let mut data = vec![0u8; width * height];
+        for y in 0..height {
+            for x in 0..width {
+                let fx = x as f64;
+                let fy = y as f64;
+                let v = 128.0
+                    + 50.0 * (fx / 23.0).sin()
+                    + 50.0 * (fy / 19.0).sin()
+                    + 30.0 * ((fx + fy) / 31.0).sin();
+                data[y * width + x] = v.clamp(0.0, 255.0) as u8;
+            }
+        }
+        data
+    }
+
+    fn shift_buffer(src: &[u8], width: usize, height: usize, dx: i32, dy: i32) -> Vec<u8> {
+        let mut out = vec![255u8; width * height];
+        for y in 0..height as i32 {
+            let sy = y - dy;
+            if sy < 0 || sy >= height as i32 {
+                continue;
+            }
+            for x in 0..width as i32 {
+                let sx = x - dx;
+                if sx < 0 || sx >= width as i32 {
+                    continue;
+                }
+                out[y as usize * width + x as usize] = src[sy as usize * width + sx as usize];
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn register_translation_recovers_known_shift_for_each_mode() {
+        let width = 48;
+        let height = 48;
+        let reference = smooth_texture(width, height);
+        let (dx, dy) = (3, -2);
+        let moving = shift_buffer(&reference, width, height, dx, dy);
+        for mode in ["diamond", "hexagon", "umh"] {
+            let (found_dx, found_dy, cost) =
+                register_translation(&reference, &moving, width, height, mode);
+            assert_eq!((found_dx, found_dy), (dx, dy), "mode {mode} failed");
+            assert!(cost < 1.0, "mode {mode} cost too high: {cost}");
+        }
+    }
+
+    #[test]
+    fn register_translation_recovers_large_diagonal_shift_for_each_mode() {
+        let width = 64;
+        let height = 64;
+        let reference = wide_period_texture(width, height);
+        let (dx, dy) = (14, -11);
+        let moving = shift_buffer(&reference, width, height, dx, dy);
+        for mode in ["diamond", "hexagon", "umh"] {
+            let (found_dx, found_dy, cost) =
+                register_translation(&reference, &moving, width, height, mode);
+            assert_eq!((found_dx, found_dy), (dx, dy), "mode {mode} failed");
+            assert!(cost < 1.0, "mode {mode} cost too high: {cost}");
+        }
+    }
+
+    #[test]
+    fn register_translation_is_deterministic() {
+        let width = 32;
+        let height = 32;
+        let reference = smooth_texture(width, height);
+        let moving = shift_buffer(&reference, width, height, 1, 1);
+        let a = register_translation(&reference, &moving, width, height, "diamond");
+        let b = register_translation(&reference, &moving, width, height, "diamond");
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn p_hash_is_deterministic_and_formats_as_16_hex_chars() {
+        let width = 64;
+        let height = 64;
+        let data = smooth_texture(width, height);
+        let hash_str = p_hash_js(Buffer::from(data.clone()), width as u32, height as u32);
+        assert_eq!(hash_str.len(), 16);
+        let a = p_hash(&data, width, height);
+        let b = p_hash(&data, width, height);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn p_hash_is_stable_under_a_one_pixel_shift() {
+        let width = 64;
+        let height = 64;
+        let reference = smooth_texture(width, height);
+        let shifted = shift_buffer(&reference, width, height, 1, 0);
+        let hash_a = p_hash(&reference, width, height);
+        let hash_b = p_hash(&shifted, width, height);
+        let distance = (hash_a ^ hash_b).count_ones();
+        assert!(distance <= 12, "hamming distance too large: {distance}");
+    }
+
+    #[test]
+    fn hamming_distance_counts_differing_bits() {
+        assert_eq!(hamming_distance_js("0".to_string(), "0".to_string()), 0);
+        assert_eq!(hamming_distance_js("ff".to_string(), "00".to_string()), 8);
+        assert_eq!(
+            hamming_distance_js("ffffffffffffffff".to_string(), "0".to_string()),
+            64
+        );
+    }
+
+    fn striped_text_lines(width: usize, height: usize) -> Vec<u8> {
+        let mut data = vec![255u8; width * height];
+        let mut y = 2usize;
+        while y < height {
+            for x in 0..width {
+                data[y * width + x] = 20;
+            }
+            y += 6;
+        }
+        data
+    }
+
+    #[test]
+    fn estimate_skew_angle_ex_projection_prefers_axis_aligned_lines() {
+        let width = 80;
+        let height = 80;
+        let data = striped_text_lines(width, height);
+        let estimate = estimate_skew_angle_ex_js(
+            Buffer::from(data),
+            width as u32,
+            height as u32,
+            "projection".to_string(),
+        );
+        assert!(estimate.angle.abs() < 1.0, "angle was {}", estimate.angle);
+        assert!(estimate.confidence > 0.0);
+    }
+
+    fn rotated_striped_text_lines(width: usize, height: usize, angle_deg: f64) -> Vec<u8> {
+        let tan_theta = angle_deg.to_radians().tan();
+        let mut data = vec![255u8; width * height];
+        for y in 0..height {
+            for x in 0..width {
+                let u = y as f64 - x as f64 * tan_theta;
+                if u.rem_euclid(6.0) < 2.0 {
+                    data[y * width + x] = 20;
+                }
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn estimate_skew_angle_ex_projection_recovers_a_known_rotation() {
+        let width = 80;
+        let height = 80;
+        let angle_deg = 4.0;
+        let data = rotated_striped_text_lines(width, height, angle_deg);
+        let estimate = estimate_skew_angle_ex_js(
+            Buffer::from(data),
+            width as u32,
+            height as u32,
+            "projection".to_string(),
+        );
+        assert!(
+            (estimate.angle - angle_deg).abs() < 0.5,
+            "angle was {}, expected close to {angle_deg}",
+            estimate.angle
+        );
+    }
+
+    #[test]
+    fn estimate_skew_angle_ex_falls_back_to_gradient_for_unknown_method() {
+        let width = 40;
+        let height = 40;
+        let data = striped_text_lines(width, height);
+        let via_unknown = estimate_skew_angle_ex_js(
+            Buffer::from(data.clone()),
+            width as u32,
+            height as u32,
+            "bogus".to_string(),
+        );
+        let via_gradient = estimate_skew_angle_js(Buffer::from(data), width as u32, height as u32);
+        assert_eq!(via_unknown.angle, via_gradient.angle);
+        assert_eq!(via_unknown.confidence, via_gradient.confidence);
+    }
+
+    #[test]
+    fn apply_deskew_is_identity_at_zero_angle() {
+        let width = 12;
+        let height = 10;
+        let data = smooth_texture(width, height);
+        let (out, out_width, out_height) = apply_deskew(&data, width, height, 0.0, 255, false);
+        assert_eq!((out_width, out_height), (width, height));
+        assert_eq!(out, data);
+    }
+
+    #[test]
+    fn apply_deskew_keeps_input_dimensions_without_expand() {
+        let width = 20;
+        let height = 16;
+        let data = smooth_texture(width, height);
+        let (out, out_width, out_height) = apply_deskew(&data, width, height, 5.0, 255, false);
+        assert_eq!((out_width, out_height), (width, height));
+        assert_eq!(out.len(), width * height);
+    }
+
+    #[test]
+    fn apply_deskew_grows_canvas_when_expand_is_set() {
+        let width = 20;
+        let height = 16;
+        let data = smooth_texture(width, height);
+        let (out, out_width, out_height) = apply_deskew(&data, width, height, 15.0, 255, true);
+        assert!(out_width > width);
+        assert!(out_height > height);
+        assert_eq!(out.len(), out_width * out_height);
+    }
 }